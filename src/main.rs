@@ -1,10 +1,15 @@
 use anyhow::{anyhow, Context, Result};
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
 use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
 use std::{
+    cell::RefCell,
     collections::HashMap,
     collections::HashSet,
-    env, fs, io,
+    collections::VecDeque,
+    env, fs,
     path::{Path, PathBuf},
     process::{Command, Stdio},
 };
@@ -16,9 +21,30 @@ struct PathAliases {
 }
 
 impl PathAliases {
-    fn new(git_root: &Path) -> Self {
+    fn new(start_dir: &Path, git_root: &Path) -> Self {
         let mut aliases = HashMap::new();
-        aliases.insert("@".to_string(), git_root.to_path_buf());
+
+        if let Some(config) = find_tsconfig(start_dir, git_root) {
+            let tsconfig = parse_tsconfig(&config);
+            let config_dir = config.parent().unwrap_or(git_root);
+            let base_dir = config_dir.join(&tsconfig.base_url);
+
+            for (alias, targets) in tsconfig.paths {
+                let Some(target) = targets.first() else {
+                    continue;
+                };
+                let alias_prefix = alias.trim_end_matches("/*").to_string();
+                let target_prefix = target.trim_end_matches("/*");
+                aliases.insert(alias_prefix, base_dir.join(target_prefix));
+            }
+        }
+
+        // `@ -> git_root` is only a fallback default; a tsconfig that
+        // defines its own `@`/`@/*` mapping takes precedence.
+        aliases
+            .entry("@".to_string())
+            .or_insert_with(|| git_root.to_path_buf());
+
         Self { aliases }
     }
 
@@ -37,38 +63,295 @@ impl PathAliases {
     }
 }
 
+/// The subset of `compilerOptions` that drives import resolution.
+#[derive(Debug, Default)]
+struct TsConfig {
+    base_url: String,
+    paths: HashMap<String, Vec<String>>,
+}
+
+/// Walks up from `start_dir` to `git_root` (inclusive) looking for the
+/// nearest `tsconfig.json` or `jsconfig.json`.
+fn find_tsconfig(start_dir: &Path, git_root: &Path) -> Option<PathBuf> {
+    let mut dir = start_dir.to_path_buf();
+    loop {
+        for name in ["tsconfig.json", "jsconfig.json"] {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+
+        if dir == git_root || !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Parses `path` and follows `extends` chains, merging `baseUrl`/`paths`
+/// with the closest config taking precedence.
+fn parse_tsconfig(path: &Path) -> TsConfig {
+    let mut config = TsConfig::default();
+    let mut visited = HashSet::new();
+    merge_tsconfig(path, &mut config, &mut visited);
+    config
+}
+
+fn merge_tsconfig(path: &Path, config: &mut TsConfig, visited: &mut HashSet<PathBuf>) {
+    let Ok(canonical) = path.canonicalize() else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+
+    let Ok(raw) = fs::read_to_string(path) else {
+        return;
+    };
+    let Ok(json) = serde_json::from_str::<Value>(&strip_json_comments(&raw)) else {
+        return;
+    };
+
+    // Apply the parent config first so the child's own settings win.
+    if let Some(extends) = json.get("extends").and_then(|v| v.as_str()) {
+        if let Some(parent_dir) = path.parent() {
+            let mut parent_path = parent_dir.join(extends);
+            if parent_path.extension().is_none() {
+                parent_path.set_extension("json");
+            }
+            merge_tsconfig(&parent_path, config, visited);
+        }
+    }
+
+    let Some(options) = json.get("compilerOptions") else {
+        return;
+    };
+
+    if let Some(base_url) = options.get("baseUrl").and_then(|v| v.as_str()) {
+        config.base_url = base_url.to_string();
+    }
+
+    if let Some(paths) = options.get("paths").and_then(|v| v.as_object()) {
+        for (alias, targets) in paths {
+            let targets = targets
+                .as_array()
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+            config.paths.insert(alias.clone(), targets);
+        }
+    }
+}
+
+/// Strips `//` and `/* */` comments so tsconfig's JSON-with-comments parses
+/// as plain JSON. Not string-literal-aware, but good enough for the
+/// `compilerOptions` blocks we actually read.
+fn strip_json_comments(input: &str) -> String {
+    let block_comments = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    let line_comments = Regex::new(r"//[^\n]*").unwrap();
+    let without_blocks = block_comments.replace_all(input, "");
+    line_comments.replace_all(&without_blocks, "").into_owned()
+}
+
+/// How `ProjectContext` decides whether a path belongs in the clump.
+#[derive(Debug)]
+enum IgnoreBackend {
+    /// Re-derives ignore status from `.gitignore` files, the way `clump`
+    /// always has.
+    Hierarchical {
+        global_ignore: Option<Gitignore>,
+        dir_ignores: RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+    },
+    /// Delegates to `git` itself: a path is in the clump iff git considers
+    /// it part of the repo. `known_dirs` holds every ancestor directory of a
+    /// known file so directory inputs can still be walked for expansion.
+    Git {
+        known: HashSet<PathBuf>,
+        known_dirs: HashSet<PathBuf>,
+    },
+}
+
 #[derive(Debug)]
 struct ProjectContext {
     git_root: PathBuf,
-    gitignore: Gitignore,
     path_aliases: PathAliases,
+    ignore_backend: IgnoreBackend,
+    languages: LanguageRegistry,
 }
 
 impl ProjectContext {
-    fn new(input_file: &Path) -> Result<Self> {
-        let git_root = find_git_root(input_file)?;
-        let gitignore = load_gitignore(&git_root)?;
-        let path_aliases = PathAliases::new(&git_root);
+    fn new(start_path: &Path, use_git_backend: bool, tracked_only: bool) -> Result<Self> {
+        let git_root = find_git_root(start_path)?;
+        let start_dir = if start_path.is_dir() {
+            start_path.to_path_buf()
+        } else {
+            start_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| git_root.clone())
+        };
+        let path_aliases = PathAliases::new(&start_dir, &git_root);
+        let languages = LanguageRegistry::load(&git_root)?;
+
+        let ignore_backend = match use_git_backend.then(|| git_known_files(&git_root, tracked_only)).flatten() {
+            Some(known) => {
+                let known_dirs = known_ancestor_dirs(&known, &git_root);
+                IgnoreBackend::Git { known, known_dirs }
+            }
+            None => IgnoreBackend::Hierarchical {
+                global_ignore: load_global_ignore(&git_root)?,
+                dir_ignores: RefCell::new(HashMap::new()),
+            },
+        };
+
         Ok(Self {
             git_root,
-            gitignore,
             path_aliases,
+            ignore_backend,
+            languages,
         })
     }
 
+    /// Returns (and caches) the compiled matcher for `dir`'s own `.gitignore`,
+    /// or `None` if that directory has no such file.
+    fn gitignore_for_dir(
+        dir_ignores: &RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+        dir: &Path,
+    ) -> Option<Gitignore> {
+        if let Some(cached) = dir_ignores.borrow().get(dir) {
+            return cached.clone();
+        }
+
+        let gitignore_path = dir.join(".gitignore");
+        let matcher = if gitignore_path.exists() {
+            let mut builder = GitignoreBuilder::new(dir);
+            builder.add(&gitignore_path);
+            builder.build().ok()
+        } else {
+            None
+        };
+
+        dir_ignores
+            .borrow_mut()
+            .insert(dir.to_path_buf(), matcher.clone());
+        matcher
+    }
+
     fn is_ignored(&self, path: &Path) -> bool {
-        if !path
-            .canonicalize()
-            .map(|p| p.starts_with(&self.git_root))
-            .unwrap_or(false)
-        {
+        let Ok(canonical) = path.canonicalize() else {
+            return true;
+        };
+        if !canonical.starts_with(&self.git_root) {
             return true;
         }
 
-        self.gitignore
-            .matched_path_or_any_parents(path, path.is_dir())
-            .is_ignore()
+        match &self.ignore_backend {
+            IgnoreBackend::Git { known, known_dirs } => {
+                if path.is_dir() {
+                    canonical != self.git_root && !known_dirs.contains(&canonical)
+                } else {
+                    !known.contains(&canonical)
+                }
+            }
+            IgnoreBackend::Hierarchical {
+                global_ignore,
+                dir_ignores,
+            } => {
+                let is_dir = path.is_dir();
+
+                // Walk from the file's own directory up to the git root,
+                // consulting each directory's `.gitignore` most-specific-first
+                // so a deeper `!keep.me` can re-include something excluded
+                // higher up. The first definite verdict wins; `Match::None`
+                // falls through to the next directory up.
+                let mut dir = if is_dir {
+                    canonical.clone()
+                } else {
+                    canonical
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .unwrap_or_else(|| self.git_root.clone())
+                };
+                loop {
+                    if let Some(gitignore) = Self::gitignore_for_dir(dir_ignores, &dir) {
+                        match gitignore.matched_path_or_any_parents(&canonical, is_dir) {
+                            Match::Ignore(_) => return true,
+                            Match::Whitelist(_) => return false,
+                            Match::None => {}
+                        }
+                    }
+
+                    if dir == self.git_root || !dir.pop() {
+                        break;
+                    }
+                }
+
+                // Nothing in the hierarchy had an opinion; fall back to
+                // `.git/info/exclude` and the user's global `core.excludesFile`.
+                global_ignore
+                    .as_ref()
+                    .map(|g| g.matched_path_or_any_parents(&canonical, is_dir).is_ignore())
+                    .unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// Runs `git ls-files` once to get the authoritative set of paths git
+/// considers part of the repo. With `tracked_only`, only files in git's
+/// index count (`--cached`); otherwise untracked-but-not-ignored files are
+/// included too (`--cached --others --exclude-standard`), matching
+/// `git status`. Returns `None` if `git` itself is unavailable, so callers
+/// can fall back to the `ignore`-crate path.
+fn git_known_files(git_root: &Path, tracked_only: bool) -> Option<HashSet<PathBuf>> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(git_root).arg("ls-files");
+    if tracked_only {
+        command.arg("--cached");
+    } else {
+        command.args(["--cached", "--others", "--exclude-standard"]);
+    }
+    command.arg("-z");
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(
+        output
+            .stdout
+            .split(|&b| b == b'\0')
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                git_root
+                    .join(String::from_utf8_lossy(entry).as_ref())
+                    .canonicalize()
+                    .ok()
+            })
+            .collect(),
+    )
+}
+
+/// `git ls-files` only ever names files, so a plain membership test would
+/// call every directory "ignored" and stop `expand_directory` from ever
+/// recursing. This collects every ancestor directory of a known file (up
+/// to `git_root`) so directories on the way to something git tracks are
+/// recognized as not ignored.
+fn known_ancestor_dirs(known: &HashSet<PathBuf>, git_root: &Path) -> HashSet<PathBuf> {
+    let mut dirs = HashSet::new();
+    for file in known {
+        let mut dir = file.parent();
+        while let Some(d) = dir {
+            if !dirs.insert(d.to_path_buf()) {
+                break;
+            }
+            if d == git_root {
+                break;
+            }
+            dir = d.parent();
+        }
     }
+    dirs
 }
 
 fn find_git_root(start_path: &Path) -> Result<PathBuf> {
@@ -90,19 +373,217 @@ fn find_git_root(start_path: &Path) -> Result<PathBuf> {
     }
 }
 
-fn load_gitignore(git_root: &Path) -> Result<Gitignore> {
+/// Computes the shared project origin for a batch of canonicalized input
+/// paths: their common path prefix.
+fn common_prefix(paths: &[PathBuf]) -> PathBuf {
+    let mut prefix = paths[0].clone();
+    for path in &paths[1..] {
+        while !path.starts_with(&prefix) {
+            if !prefix.pop() {
+                break;
+            }
+        }
+    }
+    prefix
+}
+
+/// Loads the lowest-priority, repo-wide ignore sources: `.git/info/exclude`
+/// and the user's global `core.excludesFile`, if either is set.
+fn load_global_ignore(git_root: &Path) -> Result<Option<Gitignore>> {
     let mut builder = GitignoreBuilder::new(git_root);
-    let gitignore_path = git_root.join(".gitignore");
-    if gitignore_path.exists() {
-        builder.add(gitignore_path);
+    let mut has_any = false;
+
+    let info_exclude = git_root.join(".git").join("info").join("exclude");
+    if info_exclude.exists() {
+        if let Some(err) = builder.add(&info_exclude) {
+            return Err(err.into());
+        }
+        has_any = true;
+    }
+
+    if let Some(excludes_file) = core_excludes_file(git_root) {
+        if excludes_file.exists() {
+            if let Some(err) = builder.add(&excludes_file) {
+                return Err(err.into());
+            }
+            has_any = true;
+        }
     }
-    Ok(builder.build()?)
+
+    if !has_any {
+        return Ok(None);
+    }
+
+    Ok(Some(builder.build()?))
+}
+
+/// Resolves `core.excludesFile` via `git config`, expanding a leading `~/`.
+fn core_excludes_file(git_root: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(git_root)
+        .args(["config", "--get", "core.excludesFile"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let raw = String::from_utf8(output.stdout).ok()?;
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(match trimmed.strip_prefix("~/") {
+        Some(rest) => env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(rest))
+            .unwrap_or_else(|| PathBuf::from(trimmed)),
+        None => PathBuf::from(trimmed),
+    })
+}
+
+/// How a `LanguageConfig`'s captured import text turns into a filesystem
+/// path. `Literal` is the generic fallback for languages registered via
+/// `.clump.toml` that don't need a bespoke strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportResolution {
+    Python,
+    JavaScript,
+    Literal,
 }
 
+#[derive(Debug, Clone)]
 struct LanguageConfig {
     language: tree_sitter::Language,
-    query: &'static str,
-} 
+    query: String,
+    resolution: ImportResolution,
+}
+
+const PYTHON_IMPORT_QUERY: &str = r#"
+    (import_statement
+        name: (dotted_name) @import)
+    (import_from_statement
+        module_name: (dotted_name) @import)
+"#;
+
+const TYPESCRIPT_IMPORT_QUERY: &str = r#"
+    (import_statement
+        source: (string) @import)
+    (call_expression
+        function: (identifier) @function
+        arguments: (arguments (string) @import)
+        (#eq? @function "require"))
+"#;
+
+/// Extension -> `LanguageConfig` table. Ships with Python/TS built in and
+/// is extended (or overridden) by a `.clump.toml` at the git root, so new
+/// languages can be registered without recompiling `clump`.
+#[derive(Debug)]
+struct LanguageRegistry {
+    by_extension: HashMap<String, LanguageConfig>,
+}
+
+impl LanguageRegistry {
+    fn load(git_root: &Path) -> Result<Self> {
+        let mut by_extension = Self::builtin();
+
+        let config_path = git_root.join(".clump.toml");
+        if config_path.exists() {
+            let raw = fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read {}", config_path.display()))?;
+            let config: ClumpConfig = toml::from_str(&raw)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+            for (extension, raw_language) in config.languages {
+                let language = resolve_grammar(&raw_language.language).ok_or_else(|| {
+                    anyhow!(
+                        "Unknown language '{}' for extension '{}' in .clump.toml",
+                        raw_language.language,
+                        extension
+                    )
+                })?;
+                let resolution = match raw_language.resolution.as_deref() {
+                    None | Some("literal") => ImportResolution::Literal,
+                    Some("python") => ImportResolution::Python,
+                    Some("javascript") => ImportResolution::JavaScript,
+                    Some(other) => {
+                        return Err(anyhow!(
+                            "Unknown resolution strategy '{}' for extension '{}' in .clump.toml",
+                            other,
+                            extension
+                        ))
+                    }
+                };
+
+                by_extension.insert(
+                    extension,
+                    LanguageConfig {
+                        language,
+                        query: raw_language.query,
+                        resolution,
+                    },
+                );
+            }
+        }
+
+        Ok(Self { by_extension })
+    }
+
+    fn builtin() -> HashMap<String, LanguageConfig> {
+        let mut by_extension = HashMap::new();
+        by_extension.insert(
+            "py".to_string(),
+            LanguageConfig {
+                language: tree_sitter_python::language(),
+                query: PYTHON_IMPORT_QUERY.to_string(),
+                resolution: ImportResolution::Python,
+            },
+        );
+        for extension in ["js", "ts", "jsx", "tsx"] {
+            by_extension.insert(
+                extension.to_string(),
+                LanguageConfig {
+                    language: tree_sitter_typescript::language(),
+                    query: TYPESCRIPT_IMPORT_QUERY.to_string(),
+                    resolution: ImportResolution::JavaScript,
+                },
+            );
+        }
+        by_extension
+    }
+
+    fn get(&self, extension: &str) -> Option<&LanguageConfig> {
+        self.by_extension.get(extension)
+    }
+}
+
+/// The `.clump.toml` shape: `[languages.<extension>]` entries overriding or
+/// extending the built-in table.
+#[derive(Debug, Deserialize, Default)]
+struct ClumpConfig {
+    #[serde(default)]
+    languages: HashMap<String, RawLanguageConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawLanguageConfig {
+    language: String,
+    query: String,
+    #[serde(default)]
+    resolution: Option<String>,
+}
+
+/// Maps a `.clump.toml` language name to a tree-sitter grammar compiled
+/// into this binary. Registering a language that isn't one of these still
+/// requires adding its grammar crate as a dependency and a match arm here.
+fn resolve_grammar(name: &str) -> Option<tree_sitter::Language> {
+    match name {
+        "python" => Some(tree_sitter_python::language()),
+        "typescript" | "javascript" => Some(tree_sitter_typescript::language()),
+        _ => None,
+    }
+}
 
 fn get_imports(file_path: &Path, project_ctx: &ProjectContext) -> Result<Vec<PathBuf>> {
     let content = fs::read_to_string(file_path)?;
@@ -113,41 +594,18 @@ fn get_imports(file_path: &Path, project_ctx: &ProjectContext) -> Result<Vec<Pat
         .unwrap_or("")
         .to_lowercase();
 
-    let config = match extension.as_str() {
-        "py" => Some(LanguageConfig {
-            language: tree_sitter_python::language(),
-            query: r#"
-                (import_statement
-                    name: (dotted_name) @import)
-                (import_from_statement
-                    module_name: (dotted_name) @import)
-            "#,
-        }),
-        "js" | "ts" | "jsx" | "tsx" => Some(LanguageConfig {
-            language: tree_sitter_typescript::language(),
-            query: r#"
-                (import_statement
-                    source: (string) @import)
-                (call_expression
-                    function: (identifier) @function
-                    arguments: (arguments (string) @import)
-                    (#eq? @function "require"))
-            "#,
-        }),
-        _ => None,
-    };
-
-    let Some(config) = config else {
+    let Some(config) = project_ctx.languages.get(&extension) else {
         return Ok(vec![]);
     };
 
     let mut parser = Parser::new();
     parser.set_language(config.language)?;
 
-    let tree = parser.parse(&content, None)
+    let tree = parser
+        .parse(&content, None)
         .ok_or_else(|| anyhow!("Failed to parse file"))?;
 
-    let query = Query::new(config.language, config.query)?;
+    let query = Query::new(config.language, &config.query)?;
     let mut cursor = QueryCursor::new();
     let matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
 
@@ -155,18 +613,22 @@ fn get_imports(file_path: &Path, project_ctx: &ProjectContext) -> Result<Vec<Pat
         .filter_map(|m| {
             let capture = m.captures[0];
             let import_text = capture.node.utf8_text(content.as_bytes()).ok()?;
-            
+
             // Clean up the import text (remove quotes, etc)
             let clean_import = import_text.trim_matches(|c| c == '"' || c == '\'' || c == '`');
-            
-            match extension.as_str() {
-                "py" => Some(resolve_python_import(clean_import, file_dir, &project_ctx.git_root)),
-                "js" | "ts" | "jsx" | "tsx" => {
-                    project_ctx.path_aliases
+
+            match config.resolution {
+                ImportResolution::Python => {
+                    Some(resolve_python_import(clean_import, file_dir, &project_ctx.git_root))
+                }
+                ImportResolution::JavaScript => {
+                    let base_path = project_ctx
+                        .path_aliases
                         .resolve_path(clean_import, file_dir)
-                        .or_else(|| Some(resolve_js_import(clean_import, file_dir)))
+                        .or_else(|| resolve_js_import(clean_import, file_dir));
+                    Some(base_path.and_then(|p| resolve_js_path(&p)))
                 }
-                _ => None,
+                ImportResolution::Literal => Some(Some(file_dir.join(clean_import))),
             }
         })
         .flatten()
@@ -183,15 +645,41 @@ fn resolve_python_import(import: &str, file_dir: &Path, git_root: &Path) -> Opti
     }
 }
 
-fn resolve_js_import(import: &str, file_dir: &Path) -> PathBuf {
-    let base_path = file_dir.join(import);
-    
-    // Return the base path - the existence check in get_imports will handle
-    // checking various extensions and index files
-    base_path
+fn resolve_js_import(import: &str, file_dir: &Path) -> Option<PathBuf> {
+    Some(file_dir.join(import))
+}
+
+/// Node-style resolution for a JS/TS import target: try the path as given,
+/// then each extension in turn, then `index.<ext>` inside it as a directory.
+fn resolve_js_path(base_path: &Path) -> Option<PathBuf> {
+    const EXTENSIONS: [&str; 6] = ["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+
+    if base_path.is_file() {
+        return Some(base_path.to_path_buf());
+    }
+
+    for ext in EXTENSIONS {
+        let mut with_ext = base_path.as_os_str().to_owned();
+        with_ext.push(format!(".{ext}"));
+        let candidate = PathBuf::from(with_ext);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    for ext in EXTENSIONS {
+        let candidate = base_path.join(format!("index.{ext}"));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    None
 }
 
-fn copy_to_clipboard<P: AsRef<Path>>(paths: &[P]) -> Result<()> {
+/// Concatenates each file's contents into the `<file>...</file>`-tagged
+/// blob clump has always produced, ready for any output sink.
+fn render_contents<P: AsRef<Path>>(paths: &[P]) -> Result<String> {
     let mut all_contents = String::new();
 
     for path in paths {
@@ -205,68 +693,266 @@ fn copy_to_clipboard<P: AsRef<Path>>(paths: &[P]) -> Result<()> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read file: {}", path.display()))?;
         all_contents.push_str(&content);
-        all_contents.push_str("\n");
+        all_contents.push('\n');
     }
 
-    let mut pbcopy = Command::new("pbcopy")
+    Ok(all_contents)
+}
+
+/// Where a rendered clump ends up.
+enum OutputSink {
+    Clipboard,
+    Stdout,
+    File(PathBuf),
+}
+
+/// Clipboard utilities to try, in order, across macOS (`pbcopy`), Wayland
+/// (`wl-copy`), X11 (`xclip`/`xsel`), and Windows via WSL (`clip.exe`).
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+fn detect_clipboard_command() -> Option<(&'static str, &'static [&'static str])> {
+    CLIPBOARD_COMMANDS.iter().copied().find(|(program, _)| {
+        Command::new(program)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    })
+}
+
+fn write_to_clipboard(contents: &str) -> Result<()> {
+    let (program, args) = detect_clipboard_command().ok_or_else(|| {
+        anyhow!(
+            "No clipboard utility found (tried pbcopy, wl-copy, xclip, xsel, clip.exe); \
+             use --stdout or --output instead"
+        )
+    })?;
+
+    let mut child = Command::new(program)
+        .args(args)
         .stdin(Stdio::piped())
         .spawn()
-        .context("Failed to start pbcopy")?;
+        .with_context(|| format!("Failed to start {program}"))?;
 
-    if let Some(mut stdin) = pbcopy.stdin.take() {
+    if let Some(mut stdin) = child.stdin.take() {
         use std::io::Write;
-        stdin.write_all(all_contents.as_bytes())?;
+        stdin.write_all(contents.as_bytes())?;
     }
 
-    pbcopy.wait()?;
+    child.wait()?;
 
     Ok(())
 }
 
-fn process_file(
-    file_path: &Path,
-    project_ctx: &ProjectContext,
-    processed: &mut HashSet<PathBuf>,
-) -> Result<()> {
-    let canonical_path = file_path.canonicalize()?;
+fn write_output(contents: &str, sink: &OutputSink) -> Result<()> {
+    match sink {
+        OutputSink::Clipboard => write_to_clipboard(contents),
+        OutputSink::Stdout => {
+            print!("{contents}");
+            Ok(())
+        }
+        OutputSink::File(path) => fs::write(path, contents)
+            .with_context(|| format!("Failed to write output file: {}", path.display())),
+    }
+}
+
+/// ~4 bytes per token is the usual rough estimate for LLM context budgeting.
+fn estimate_tokens(path: &Path) -> usize {
+    fs::metadata(path).map(|m| m.len() as usize / 4).unwrap_or(0)
+}
+
+/// Keeps files in `ordered` (entrypoint first, then nearest imports) until
+/// `budget` tokens are spent, returning `(included, dropped)`. The
+/// entrypoint is always included, even if it alone overflows the budget.
+/// Beyond that, stops at the first file that would overflow the budget
+/// rather than packing smaller, lower-priority files in ahead of it, so
+/// the dependency order is preserved in what's dropped as well as what's
+/// kept. With no budget, every file is included.
+fn apply_token_budget(ordered: Vec<PathBuf>, budget: Option<usize>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let Some(budget) = budget else {
+        return (ordered, Vec::new());
+    };
+
+    let mut included = Vec::new();
+    let mut dropped = Vec::new();
+    let mut spent = 0usize;
+    let mut iter = ordered.into_iter();
 
-    if processed.contains(&canonical_path) || project_ctx.is_ignored(file_path) {
-        return Ok(());
+    // The entrypoint always makes it in, even if it alone overflows the
+    // budget — an empty clump isn't a useful result.
+    if let Some(entrypoint) = iter.next() {
+        spent += estimate_tokens(&entrypoint);
+        included.push(entrypoint);
     }
 
-    processed.insert(canonical_path);
+    for file in iter.by_ref() {
+        let tokens = estimate_tokens(&file);
+        if spent + tokens > budget {
+            dropped.push(file);
+            break;
+        }
+        spent += tokens;
+        included.push(file);
+    }
+    dropped.extend(iter);
+
+    (included, dropped)
+}
+
+/// Breadth-first walk from `roots` through their transitive imports,
+/// de-duplicated by canonical path across the whole batch. Breadth-first
+/// keeps the result in entrypoint-first, nearest-imports-next order, which
+/// `apply_token_budget` relies on to drop the least relevant files first.
+fn collect_files(roots: &[PathBuf], project_ctx: &ProjectContext) -> Result<Vec<PathBuf>> {
+    let mut ordered = Vec::new();
+    let mut processed = HashSet::new();
+    let mut queue: VecDeque<PathBuf> = roots.iter().cloned().collect();
+
+    while let Some(file_path) = queue.pop_front() {
+        let Ok(canonical_path) = file_path.canonicalize() else {
+            continue;
+        };
+        if processed.contains(&canonical_path) || project_ctx.is_ignored(&file_path) {
+            continue;
+        }
+        processed.insert(canonical_path);
+        ordered.push(file_path.clone());
 
-    for import in get_imports(file_path, project_ctx)? {
-        process_file(&import, project_ctx, processed)?;
+        for import in get_imports(&file_path, project_ctx)? {
+            queue.push_back(import);
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Recursively expands a directory input into its contained source files,
+/// skipping anything `project_ctx` considers ignored and any file whose
+/// extension isn't registered with the `LanguageRegistry` (binaries,
+/// images, etc. aren't valid UTF-8 and would otherwise blow up
+/// `render_contents`).
+fn expand_directory(dir: &Path, project_ctx: &ProjectContext, roots: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if project_ctx.is_ignored(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            expand_directory(&path, project_ctx, roots)?;
+        } else if is_known_source_file(&path, project_ctx) {
+            roots.push(path);
+        }
     }
 
     Ok(())
 }
 
+fn is_known_source_file(path: &Path, project_ctx: &ProjectContext) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| project_ctx.languages.get(&ext.to_lowercase()).is_some())
+}
+
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
-    if args.len() != 2 {
-        return Err(anyhow!("Usage: {} <file>", args[0]));
+
+    let mut use_git_backend = false;
+    let mut tracked_only = false;
+    let mut sink = OutputSink::Clipboard;
+    let mut token_budget = None;
+    let mut positional = Vec::new();
+
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--git" => use_git_backend = true,
+            "--tracked-only" => {
+                use_git_backend = true;
+                tracked_only = true;
+            }
+            "--stdout" => sink = OutputSink::Stdout,
+            "--output" => {
+                let path = rest
+                    .next()
+                    .ok_or_else(|| anyhow!("--output requires a file path"))?;
+                sink = OutputSink::File(PathBuf::from(path));
+            }
+            "--budget" => {
+                let value = rest
+                    .next()
+                    .ok_or_else(|| anyhow!("--budget requires a token count"))?;
+                token_budget =
+                    Some(value.parse::<usize>().context("--budget must be a number")?);
+            }
+            other => positional.push(other),
+        }
     }
 
-    let input_file = PathBuf::from(&args[1]);
-    if !input_file.exists() {
-        return Err(anyhow!("File not found: {}", input_file.display()));
+    if positional.is_empty() {
+        return Err(anyhow!(
+            "Usage: {} [--git] [--tracked-only] [--stdout | --output <file>] [--budget <tokens>] <path>...",
+            args[0]
+        ));
     }
 
-    let project_ctx = ProjectContext::new(&input_file)?;
-    let mut processed_files = HashSet::new();
+    let input_paths = positional
+        .iter()
+        .map(|raw| {
+            let path = PathBuf::from(raw);
+            path.canonicalize()
+                .with_context(|| format!("Path not found: {}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let project_origin = common_prefix(&input_paths);
+    let project_ctx = ProjectContext::new(&project_origin, use_git_backend, tracked_only)?;
+
+    let mut roots = Vec::new();
+    for path in &input_paths {
+        if path.is_dir() {
+            expand_directory(path, &project_ctx, &mut roots)?;
+        } else {
+            roots.push(path.clone());
+        }
+    }
 
-    process_file(&input_file, &project_ctx, &mut processed_files)?;
+    let ordered_files = collect_files(&roots, &project_ctx)?;
+    let (included_files, dropped_files) = apply_token_budget(ordered_files, token_budget);
 
-    println!("\nFiles to be copied:");
-    for file in &processed_files {
-        println!("- {}", file.display());
+    eprintln!("\nFiles to be copied:");
+    for file in &included_files {
+        eprintln!("- {}", file.display());
     }
-    println!();
+    if !dropped_files.is_empty() {
+        eprintln!(
+            "\nDropped {} file(s) to stay within the token budget:",
+            dropped_files.len()
+        );
+        for file in &dropped_files {
+            eprintln!("- {}", file.display());
+        }
+    }
+    eprintln!();
+
+    let contents = render_contents(&included_files)?;
+    write_output(&contents, &sink)?;
 
-    copy_to_clipboard(&processed_files.into_iter().collect::<Vec<_>>())?;
-    println!("File and dependencies copied to clipboard");
+    match sink {
+        OutputSink::Clipboard => eprintln!("File and dependencies copied to clipboard"),
+        OutputSink::Stdout => {}
+        OutputSink::File(path) => {
+            eprintln!("File and dependencies written to {}", path.display())
+        }
+    }
 
     Ok(())
 }